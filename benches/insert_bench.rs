@@ -0,0 +1,55 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rand::{thread_rng, Rng};
+use skiplist::OrderedSkipList;
+use uuid::Uuid;
+
+use lseq::{BitBase, DoubleBase, Ident, IdentGenerator, LSEQGenerator};
+
+fn generate_idents(count: usize) -> Vec<Ident<Uuid>> {
+  let base = DoubleBase::new(None);
+  let mut gen = LSEQGenerator::with_seed(base, None, 42);
+  let replica = Uuid::new_v4();
+
+  let mut list = OrderedSkipList::<Ident<Uuid>>::new();
+  let mut rng = thread_rng();
+
+  for _i in 0..count {
+    let len = list.len();
+    let n = if len > 0 { rng.gen_range(0, len) } else { 0 };
+
+    let ident = {
+      let left = list.get(&(n));
+      let right = list.get(&(n + 1));
+      gen.generate(replica, left, right)
+    };
+
+    list.insert(ident);
+  }
+
+  list.iter().cloned().collect()
+}
+
+fn insert_100k(c: &mut Criterion) {
+  c.bench_function("insert_100k", |b| {
+    b.iter(|| black_box(generate_idents(100_000)));
+  });
+}
+
+/// Exercises `DoubleBase::split` (the bit-window rewrite) directly, by
+/// splitting every digit from a generated 100k-ident sequence back into
+/// its per-depth components.
+fn split_100k(c: &mut Criterion) {
+  let base = DoubleBase::new(None);
+  let idents = generate_idents(100_000);
+
+  c.bench_function("split_100k", |b| {
+    b.iter(|| {
+      for ident in &idents {
+        black_box(base.split(&ident.digit));
+      }
+    });
+  });
+}
+
+criterion_group!(benches, insert_100k, split_100k);
+criterion_main!(benches);
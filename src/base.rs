@@ -44,23 +44,22 @@ impl BitBase for DoubleBase {
   }
 
   fn split(&self, digit: &Integer) -> Vec<Integer> {
-    let digit = digit.clone();
-    let size = digit.significant_bits() - 1;
+    let total_bits = digit.significant_bits() - 1;
     let mut depth = 0;
-    while size != self.get_bits(depth) {
+    while total_bits != self.get_bits(depth) {
       depth += 1;
     }
 
-    let mut components = vec![];
-    let mut string = digit.to_string_radix(2)[1..].to_string();
-    let mut skip = 1;
+    let mut components = Vec::with_capacity(depth as usize + 1);
+    let mut previous_bits = 0;
     for i in 0..(depth + 1) {
-      let size = self.get_bits(i) - skip;
-      let clone = string.clone();
-      let (front, rest) = clone.split_at(size as usize + 1);
-      string = rest.to_string();
-      skip += size + 1;
-      components.push(Integer::from(Integer::parse_radix(front, 2).unwrap()))
+      let bits = self.get_bits(i);
+      let width = bits - previous_bits;
+      let offset = total_bits - bits;
+      let mask = (Integer::from(1) << width) - 1;
+
+      components.push((digit.clone() >> offset) & mask);
+      previous_bits = bits;
     }
 
     components
@@ -116,6 +115,9 @@ mod tests {
     left.set_bit(base.get_bits(0), true);
     right.set_bit(base.get_bits(0), true);
 
-    assert_eq!(base.interval(&left, &right, 0), Integer::from(30))
+    assert_eq!(base.interval(&left, &right, 0), Integer::from(30));
+
+    assert_eq!(base.split(&left), vec![Integer::from(0)]);
+    assert_eq!(base.split(&right), vec![Integer::from(31)]);
   }
 }
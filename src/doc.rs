@@ -0,0 +1,148 @@
+use std::collections::BTreeSet;
+
+use super::base::{BitBase, DoubleBase};
+use super::ident::Ident;
+use super::lseq::{IdentGenerator, LSEQGenerator};
+use super::meta::Meta;
+
+/// A self-describing mutation produced by a [`LSEQDocument`], ready to be
+/// shipped to other replicas and folded back in with
+/// [`LSEQDocument::apply`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Op<T, M: Meta> {
+  Insert(Ident<M>, T),
+  Delete(Ident<M>),
+}
+
+/// An LSEQ-ordered sequence of values, built on top of [`LSEQGenerator`].
+///
+/// Unlike a bare `IdentGenerator`, a `LSEQDocument` keeps the actual
+/// `(Ident<M>, T)` pairs in sorted order, so it can be used directly as a
+/// collaborative list/text CRDT: local edits go through
+/// [`insert`](LSEQDocument::insert)/[`delete`](LSEQDocument::delete) and
+/// produce an [`Op`] to send elsewhere, while remote edits are replayed
+/// with [`apply`](LSEQDocument::apply)/[`merge`](LSEQDocument::merge).
+pub struct LSEQDocument<T, M: Meta, B: BitBase = DoubleBase> {
+  replica: M,
+  generator: LSEQGenerator<B>,
+  entries: Vec<(Ident<M>, T)>,
+  tombstones: BTreeSet<Ident<M>>,
+}
+
+impl<T, M: Meta, B: BitBase> LSEQDocument<T, M, B> {
+  pub fn new(replica: M, generator: LSEQGenerator<B>) -> LSEQDocument<T, M, B> {
+    LSEQDocument {
+      replica,
+      generator,
+      entries: Vec::new(),
+      tombstones: BTreeSet::new(),
+    }
+  }
+
+  pub fn len(&self) -> usize {
+    self.entries.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item = &T> {
+    self.entries.iter().map(|(_, value)| value)
+  }
+
+  pub fn values(&self) -> Vec<T>
+  where
+    T: Clone,
+  {
+    self.entries.iter().map(|(_, value)| value.clone()).collect()
+  }
+
+  fn position(&self, ident: &Ident<M>) -> Result<usize, usize> {
+    self.entries.binary_search_by(|(existing, _)| existing.cmp(ident))
+  }
+
+  /// Allocates an `Ident` between the current neighbors of `index` and
+  /// inserts `value` there, returning the `Op` to ship to other replicas.
+  pub fn insert(&mut self, index: usize, value: T) -> Op<T, M>
+  where
+    T: Clone,
+  {
+    let left = if index == 0 {
+      None
+    } else {
+      self.entries.get(index - 1).map(|(ident, _)| ident)
+    };
+    let right = self.entries.get(index).map(|(ident, _)| ident);
+
+    let ident = self.generator.generate(self.replica.clone(), left, right);
+    self.entries.insert(index, (ident.clone(), value.clone()));
+
+    Op::Insert(ident, value)
+  }
+
+  /// Removes the value at `index`, tombstoning its `Ident` so a concurrent
+  /// insert of the same `Ident`, or a re-delivered delete, is a no-op.
+  pub fn delete(&mut self, index: usize) -> Op<T, M> {
+    let (ident, _) = self.entries.remove(index);
+    self.tombstones.insert(ident.clone());
+
+    Op::Delete(ident)
+  }
+
+  /// Replays a single remote `Op`, converging with any other replica that
+  /// has applied the same set of ops, regardless of delivery order.
+  pub fn apply(&mut self, op: Op<T, M>) {
+    match op {
+      Op::Insert(ident, value) => {
+        if self.tombstones.contains(&ident) {
+          return;
+        }
+        if let Err(pos) = self.position(&ident) {
+          self.entries.insert(pos, (ident, value));
+        }
+      }
+      Op::Delete(ident) => {
+        if let Ok(pos) = self.position(&ident) {
+          self.entries.remove(pos);
+        }
+        self.tombstones.insert(ident);
+      }
+    }
+  }
+
+  /// Replays a batch of remote `Op`s, in order.
+  pub fn merge<I: IntoIterator<Item = Op<T, M>>>(&mut self, ops: I) {
+    for op in ops {
+      self.apply(op);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{DoubleBase, LSEQDocument, LSEQGenerator};
+
+  #[test]
+  fn converges_across_replicas() {
+    let mut a = LSEQDocument::new("a".to_string(), LSEQGenerator::new(DoubleBase::new(None), None, 42));
+    let mut b = LSEQDocument::new("b".to_string(), LSEQGenerator::new(DoubleBase::new(None), None, 42));
+
+    let op1 = a.insert(0, "hello".to_string());
+    let op2 = a.insert(1, "world".to_string());
+
+    b.apply(op1);
+    b.apply(op2);
+
+    assert_eq!(a.values(), b.values());
+    assert_eq!(a.len(), 2);
+
+    let del = a.delete(0);
+    b.apply(del.clone());
+    // Re-delivering the same delete must stay idempotent.
+    b.apply(del);
+
+    assert_eq!(a.values(), b.values());
+    assert_eq!(a.len(), 1);
+  }
+}
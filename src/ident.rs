@@ -5,12 +5,41 @@ use rug::Integer;
 use super::base::{BitBase};
 use super::meta::{Meta};
 
+#[cfg(feature = "serde")]
+use std::convert::TryInto;
+#[cfg(feature = "serde")]
+use serde::de::DeserializeOwned;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
 #[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Ident<M: Meta> {
   pub meta: M,
+  #[cfg_attr(feature = "serde", serde(with = "digit_bytes"))]
   pub digit: Integer,
 }
 
+/// (De)serializes `Integer` as its big-endian digit bytes (via
+/// `to_digits`/`from_digits`) instead of round-tripping through a decimal
+/// or radix string, so `Ident` stays `serde`-compatible without the extra
+/// allocation a string representation would cost.
+#[cfg(feature = "serde")]
+mod digit_bytes {
+  use rug::integer::Order;
+  use rug::Integer;
+  use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+  pub fn serialize<S: Serializer>(digit: &Integer, serializer: S) -> Result<S::Ok, S::Error> {
+    digit.to_digits::<u8>(Order::MsfBe).serialize(serializer)
+  }
+
+  pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Integer, D::Error> {
+    let bytes = Vec::<u8>::deserialize(deserializer)?;
+    Ok(Integer::from_digits(&bytes, Order::MsfBe))
+  }
+}
+
 impl<M: Meta> fmt::Debug for Ident<M> {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     let mut digit = self.digit.clone();
@@ -24,7 +53,7 @@ impl<M: Meta> Ident<M> {
     Ident { meta, digit }
   }
 
-  pub fn debug(&self, base: &BitBase) -> String {
+  pub fn debug(&self, base: &dyn BitBase) -> String {
     format!(
       "[Ident meta={:?} digit={:?}]",
       self.meta,
@@ -33,6 +62,153 @@ impl<M: Meta> Ident<M> {
   }
 }
 
+#[cfg(feature = "serde")]
+impl<M: Meta + Serialize> Ident<M> {
+  /// Encodes this `Ident` into a compact, self-contained binary form: the
+  /// digit is written as its `base.split` components packed into
+  /// variable-width bit fields (one per depth, sized via `get_bits`)
+  /// instead of going through a base-2 string, followed by a
+  /// length-prefixed `meta`.
+  pub fn encode(&self, base: &dyn BitBase) -> Vec<u8> {
+    let components = base.split(&self.digit);
+    let depth = (components.len() - 1) as u32;
+
+    let mut writer = BitWriter::new();
+    writer.write_u32(depth);
+    for (i, component) in components.iter().enumerate() {
+      let width = field_width(base, i as u32);
+      writer.write_bits(component, width);
+    }
+
+    let mut bytes = writer.into_bytes();
+    let meta = bincode::serialize(&self.meta).expect("meta is serializable");
+    bytes.extend_from_slice(&(meta.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&meta);
+    bytes
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<M: Meta + DeserializeOwned> Ident<M> {
+  /// Reconstructs the `Ident` produced by `encode`, including the sentinel
+  /// high bit `pick_interval` sets on every digit.
+  pub fn decode(base: &dyn BitBase, bytes: &[u8]) -> Ident<M> {
+    let mut reader = BitReader::new(bytes);
+    let depth = reader.read_u32();
+
+    let mut digit = Integer::from(1);
+    for i in 0..=depth {
+      let width = field_width(base, i);
+      let value = reader.read_bits(width);
+      digit = (digit << width) | value;
+    }
+
+    let meta_start = reader.byte_offset();
+    let meta_len = u32::from_le_bytes(
+      bytes[meta_start..meta_start + 4]
+        .try_into()
+        .expect("encoded meta length is 4 bytes"),
+    ) as usize;
+    let meta_bytes = &bytes[meta_start + 4..meta_start + 4 + meta_len];
+    let meta = bincode::deserialize(meta_bytes).expect("meta bytes are valid");
+
+    Ident::new(meta, digit)
+  }
+}
+
+#[cfg(feature = "serde")]
+fn field_width(base: &dyn BitBase, depth: u32) -> u32 {
+  if depth == 0 {
+    base.get_bits(0)
+  } else {
+    base.get_bits(depth) - base.get_bits(depth - 1)
+  }
+}
+
+/// Minimal MSB-first bit packer backing `Ident::encode`.
+#[cfg(feature = "serde")]
+struct BitWriter {
+  bytes: Vec<u8>,
+  bit_len: usize,
+}
+
+#[cfg(feature = "serde")]
+impl BitWriter {
+  fn new() -> BitWriter {
+    BitWriter {
+      bytes: Vec::new(),
+      bit_len: 0,
+    }
+  }
+
+  /// Writes the low `width` bits of `value`, MSB first. `value` is a
+  /// `rug::Integer` (not `u64`) so fields wider than 64 bits — which
+  /// happen once `depth` grows large enough, since a field is
+  /// `get_bits(depth) - get_bits(depth - 1)` (that is, `get_base(depth)`)
+  /// wide — are written correctly instead of panicking
+  /// or silently overflowing.
+  fn write_bits(&mut self, value: &Integer, width: u32) {
+    for i in (0..width).rev() {
+      self.write_bit(value.get_bit(i));
+    }
+  }
+
+  fn write_u32(&mut self, value: u32) {
+    self.write_bits(&Integer::from(value), 32);
+  }
+
+  fn write_bit(&mut self, bit: bool) {
+    let byte_index = self.bit_len / 8;
+    if byte_index == self.bytes.len() {
+      self.bytes.push(0);
+    }
+    if bit {
+      self.bytes[byte_index] |= 1 << (7 - (self.bit_len % 8));
+    }
+    self.bit_len += 1;
+  }
+
+  fn into_bytes(self) -> Vec<u8> {
+    self.bytes
+  }
+}
+
+/// Minimal MSB-first bit reader backing `Ident::decode`.
+#[cfg(feature = "serde")]
+struct BitReader<'a> {
+  bytes: &'a [u8],
+  bit_pos: usize,
+}
+
+#[cfg(feature = "serde")]
+impl<'a> BitReader<'a> {
+  fn new(bytes: &'a [u8]) -> BitReader<'a> {
+    BitReader { bytes, bit_pos: 0 }
+  }
+
+  /// Reads `width` bits, MSB first, into a `rug::Integer` so fields wider
+  /// than 64 bits round-trip exactly instead of truncating.
+  fn read_bits(&mut self, width: u32) -> Integer {
+    let mut value = Integer::from(0);
+    for _ in 0..width {
+      let byte_index = self.bit_pos / 8;
+      let bit = (self.bytes[byte_index] >> (7 - (self.bit_pos % 8))) & 1 == 1;
+      value <<= 1;
+      value.set_bit(0, bit);
+      self.bit_pos += 1;
+    }
+    value
+  }
+
+  fn read_u32(&mut self) -> u32 {
+    self.read_bits(32).to_u32().expect("encoded depth fits in u32")
+  }
+
+  fn byte_offset(&self) -> usize {
+    (self.bit_pos + 7) / 8
+  }
+}
+
 impl<M: Meta> PartialOrd for Ident<M> {
   fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
     Some(self.cmp(other))
@@ -68,4 +244,70 @@ impl<M: Meta> Ord for Ident<M> {
 
     return self_length.cmp(&other_length);
   }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod wire_tests {
+  use rug::Integer;
+
+  use super::Ident;
+  use crate::base::DoubleBase;
+
+  fn assert_round_trips(meta: u32, digit: Integer) {
+    let base = DoubleBase::new(None);
+    let ident = Ident::new(meta, digit);
+
+    let bytes = ident.encode(&base);
+    let decoded = Ident::<u32>::decode(&base, &bytes);
+
+    assert_eq!(decoded.meta, ident.meta);
+    assert_eq!(decoded.digit, ident.digit);
+  }
+
+  #[test]
+  fn round_trips_an_empty_depth0_digit() {
+    // Sentinel bit only, no payload bits set.
+    let mut digit = Integer::from(0);
+    digit.set_bit(5, true);
+
+    assert_round_trips(7, digit);
+  }
+
+  #[test]
+  fn round_trips_a_multi_depth_digit() {
+    // Sentinel at depth 1 (bit 11), with payload bits spread across both
+    // the depth-0 (bits 6..10) and depth-1 (bits 0..5) fields.
+    let mut digit = Integer::from(0);
+    digit.set_bit(11, true);
+    digit.set_bit(9, true);
+    digit.set_bit(6, true);
+    digit.set_bit(3, true);
+    digit.set_bit(0, true);
+
+    assert_round_trips(42, digit);
+  }
+
+  #[test]
+  fn round_trips_a_non_trivial_meta() {
+    let mut digit = Integer::from(0);
+    digit.set_bit(5, true);
+    digit.set_bit(2, true);
+
+    assert_round_trips(u32::max_value(), digit);
+  }
+
+  #[test]
+  fn serde_json_round_trips() {
+    let mut digit = Integer::from(0);
+    digit.set_bit(11, true);
+    digit.set_bit(8, true);
+    digit.set_bit(1, true);
+
+    let ident = Ident::new(99u32, digit);
+    let json = serde_json::to_string(&ident).expect("Ident is serializable");
+    let decoded: Ident<u32> = serde_json::from_str(&json).expect("Ident is deserializable");
+
+    assert_eq!(decoded.meta, ident.meta);
+    assert_eq!(decoded.digit, ident.digit);
+  }
 }
\ No newline at end of file
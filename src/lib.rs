@@ -1,6 +1,16 @@
 extern crate rug;
 extern crate rand;
 
+#[cfg(feature = "serde")]
+extern crate serde;
+
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde_derive;
+
+#[cfg(feature = "serde")]
+extern crate bincode;
+
 #[cfg(test)]
 extern crate uuid;
 
@@ -10,14 +20,19 @@ extern crate skiplist;
 #[cfg(test)]
 extern crate average;
 
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
+
 mod base;
 mod strategy;
 mod lseq;
 mod ident;
 mod meta;
+mod doc;
 
 pub use base::*;
 pub use lseq::*;
 pub use ident::*;
 pub use meta::*;
 pub use strategy::*;
+pub use doc::*;
@@ -3,7 +3,7 @@ use rug::rand::RandState;
 use rug::Integer;
 
 use super::base::{BitBase, DoubleBase};
-use super::strategy::LSEQStrategy;
+use super::strategy::{default_strategy_for_depth, AllocationStrategy, Direction};
 use super::ident::{Ident};
 use super::meta::{Meta};
 
@@ -15,31 +15,55 @@ pub trait IdentGenerator<M: Meta> {
 pub struct LSEQGenerator<B: BitBase = DoubleBase> {
   base: B,
   boundary: u32,
-  strategies: HashMap<usize, LSEQStrategy>,
+  seed: u64,
+  rand: RandState<'static>,
+  strategies: HashMap<usize, Box<dyn AllocationStrategy>>,
 }
 
 impl<B: BitBase> LSEQGenerator<B> {
-  pub fn new<T: Into<Option<u32>>>(base: B, boundary: T) -> LSEQGenerator<B> {
+  /// Creates a generator whose per-depth strategy is derived from `seed`,
+  /// so any other replica constructed with the same seed allocates
+  /// identifiers in the same direction at every depth. Equivalent to
+  /// [`with_seed`](LSEQGenerator::with_seed).
+  pub fn new<T: Into<Option<u32>>>(base: B, boundary: T, seed: u64) -> LSEQGenerator<B> {
+    LSEQGenerator::with_seed(base, boundary, seed)
+  }
+
+  /// Creates a generator whose strategy selection *and* random interval
+  /// draws are both pure functions of `seed`, so two replicas built with
+  /// the same seed (and fed the same sequence of edits) produce byte-for-byte
+  /// identical `Ident`s. Benchmarks and property tests should use this
+  /// constructor to get a reproducible allocation sequence.
+  pub fn with_seed<T: Into<Option<u32>>>(base: B, boundary: T, seed: u64) -> LSEQGenerator<B> {
+    let mut rand = RandState::new();
+    rand.seed(&Integer::from(seed));
+
     LSEQGenerator {
       base: base,
       boundary: boundary.into().unwrap_or(10),
+      seed: seed,
+      rand: rand,
       strategies: HashMap::new(),
     }
   }
 
-  fn get_strategy(&self, depth: u32) -> LSEQStrategy {
-    let strategy = self.strategies.get(&(depth as usize));
-    return strategy.unwrap().clone();
-  }
-
   fn ensure_strategy(&mut self, depth: u32) {
-    if self.strategies.get(&(depth as usize)).is_none() {
-      let random = LSEQStrategy::random();
-      self.strategies.insert(depth as usize, random.clone());
+    if !self.strategies.contains_key(&(depth as usize)) {
+      let strategy = default_strategy_for_depth(depth, self.seed);
+      self.strategies.insert(depth as usize, strategy);
     }
   }
 
-  fn pick_interval<M: Meta>(&self, left: Option<&Ident<M>>, right: Option<&Ident<M>>) -> (Integer, u32, Integer, Integer) {
+  /// Overrides the allocation strategy used at `depth`, letting callers
+  /// tune the density/locality tradeoff (e.g. a [`BoundaryStrategy`] in
+  /// hot regions) instead of relying on the seed-derived default.
+  ///
+  /// [`BoundaryStrategy`]: super::strategy::BoundaryStrategy
+  pub fn set_strategy(&mut self, depth: u32, strategy: Box<dyn AllocationStrategy>) {
+    self.strategies.insert(depth as usize, strategy);
+  }
+
+  fn pick_interval<M: Meta>(&self, left: Option<&Ident<M>>, right: Option<&Ident<M>>) -> (u32, Integer, Integer, Integer) {
     let mut interval = Integer::from(0);
     let mut depth: u32 = 0;
 
@@ -67,15 +91,7 @@ impl<B: BitBase> LSEQGenerator<B> {
 
     depth -= 1;
 
-    let step = interval
-      .clone()
-      .min(Integer::from(self.boundary))
-      .max(Integer::from(1));
-
-    let mut rand = RandState::new();
-    let delta = step.clone().random_below(&mut rand) + 1;
-
-    (delta, depth, left_v, right_v)
+    (depth, left_v, right_v, interval)
   }
 }
 
@@ -86,17 +102,18 @@ impl<B: BitBase, M: Meta> IdentGenerator<M> for LSEQGenerator<B> {
     left: Option<&Ident<M>>,
     right: Option<&Ident<M>>,
   ) -> Ident<M> {
-    let (delta, depth, left_v, right_v) = self.pick_interval(left, right);
+    let (depth, left_v, right_v, interval) = self.pick_interval(left, right);
 
     self.ensure_strategy(depth);
-    let strategy = self.get_strategy(depth);
+    let strategy = self.strategies.get(&(depth as usize)).unwrap();
+    let (direction, delta) = strategy.allocate(depth, &left_v, &right_v, &interval, self.boundary, &mut self.rand);
 
-    let res = match strategy {
-      LSEQStrategy::AddFromLeft => {
+    let res = match direction {
+      Direction::FromLeft => {
         let left_n = self.base.normalize(left_v, depth);
         Ident::new(replica.clone(), left_n + delta)
       }
-      LSEQStrategy::SubtractFromRight => {
+      Direction::FromRight => {
         let right_n = self.base.normalize(right_v, depth);
         Ident::new(replica.clone(), right_n - delta)
       }
@@ -116,12 +133,13 @@ mod tests {
   use rand::{thread_rng, Rng};
   use average::{Max, Min, Mean};
 
-  use super::{DoubleBase, Ident, IdentGenerator, LSEQGenerator};
+  use super::super::strategy::BoundaryStrategy;
+  use super::{Direction, DoubleBase, Ident, IdentGenerator, LSEQGenerator};
 
   #[test]
   fn it_works() {
     let base = DoubleBase::new(None);
-    let mut gen = LSEQGenerator::new(base, None);
+    let mut gen = LSEQGenerator::new(base, None, 42);
     let replica = Uuid::new_v4();
 
     let mut list = OrderedSkipList::<Ident<Uuid>>::new();
@@ -148,5 +166,34 @@ mod tests {
 
     // list.iter().for_each(|i| println!("{:?}", i.debug(&gen.base)));
   }
-  
+
+  #[test]
+  fn with_seed_is_reproducible() {
+    let replica = Uuid::new_v4();
+
+    let mut a = LSEQGenerator::with_seed(DoubleBase::new(None), None, 7);
+    let mut b = LSEQGenerator::with_seed(DoubleBase::new(None), None, 7);
+
+    for _ in 0..50 {
+      let ident_a = a.generate(replica, None, None);
+      let ident_b = b.generate(replica, None, None);
+      assert_eq!(ident_a.digit, ident_b.digit);
+    }
+  }
+
+  #[test]
+  fn custom_strategy_overrides_the_default() {
+    // Seed 2 picks AddFromLeft at depth 0, which would place the first
+    // ident in 33..=42; the override must place it in 53..=62 instead.
+    let mut gen = LSEQGenerator::with_seed(DoubleBase::new(None), None, 2);
+    gen.set_strategy(0, Box::new(BoundaryStrategy::new(Direction::FromRight, 2)));
+
+    let replica = Uuid::new_v4();
+    let right = gen.generate(replica, None, None);
+    assert!(right.digit > 48);
+    let left = gen.generate(replica, None, Some(&right));
+
+    // BoundaryStrategy::FromRight always normalizes from the right bound.
+    assert!(left.digit < right.digit);
+  }
 }
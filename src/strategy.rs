@@ -1,20 +1,175 @@
-use rand::{self, Rng};
-
-/// The identifier allocation strategy to use at a specified depth.
-#[derive(Clone, PartialEq)]
-pub enum LSEQStrategy {
-  /// Generate identifiers by adding a value to the previous digit.
-  AddFromLeft,
-  /// Generate identifiers by subtracting a value to the next digit.
-  SubtractFromRight,
-}
-
-impl LSEQStrategy {
-  pub fn random() -> LSEQStrategy {
-    let mut rng = rand::thread_rng();
-    rng
-      .choose(&[LSEQStrategy::AddFromLeft, LSEQStrategy::SubtractFromRight])
-      .unwrap()
-      .clone()
+use rug::rand::RandState;
+use rug::Integer;
+
+/// Which side of the interval a newly-allocated identifier sits relative
+/// to, i.e. what the returned offset is added to or subtracted from.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Direction {
+  FromLeft,
+  FromRight,
+}
+
+/// A per-depth identifier allocation policy: given the normalized `left`
+/// and `right` bounds at `depth`, the available `interval` between them,
+/// and the generator's `boundary`, decides how far into the interval —
+/// and from which side — the next identifier should land.
+pub trait AllocationStrategy {
+  fn allocate(
+    &self,
+    depth: u32,
+    left: &Integer,
+    right: &Integer,
+    interval: &Integer,
+    boundary: u32,
+    rand: &mut RandState,
+  ) -> (Direction, Integer);
+}
+
+fn random_offset(interval: &Integer, boundary: u32, rand: &mut RandState) -> Integer {
+  let step = interval
+    .clone()
+    .min(Integer::from(boundary))
+    .max(Integer::from(1));
+
+  step.random_below(rand) + 1
+}
+
+/// Like `random_offset`, but skewed toward the low end of `[1, step]` by
+/// taking the smaller of two draws. `BoundaryStrategy` uses this so the
+/// generated offset stays small relative to whichever bound it normalizes
+/// from, clustering identifiers near that endpoint instead of spreading
+/// uniformly across the (now depth-grown) boundary.
+fn skewed_offset(interval: &Integer, boundary: u32, rand: &mut RandState) -> Integer {
+  let step = interval
+    .clone()
+    .min(Integer::from(boundary))
+    .max(Integer::from(1));
+
+  let a = step.clone().random_below(rand);
+  let b = step.random_below(rand);
+  a.min(b) + 1
+}
+
+/// Generate identifiers by adding a value to the previous digit.
+pub struct AddFromLeft;
+
+impl AllocationStrategy for AddFromLeft {
+  fn allocate(
+    &self,
+    _depth: u32,
+    _left: &Integer,
+    _right: &Integer,
+    interval: &Integer,
+    boundary: u32,
+    rand: &mut RandState,
+  ) -> (Direction, Integer) {
+    (Direction::FromLeft, random_offset(interval, boundary, rand))
+  }
+}
+
+/// Generate identifiers by subtracting a value from the next digit.
+pub struct SubtractFromRight;
+
+impl AllocationStrategy for SubtractFromRight {
+  fn allocate(
+    &self,
+    _depth: u32,
+    _left: &Integer,
+    _right: &Integer,
+    interval: &Integer,
+    boundary: u32,
+    rand: &mut RandState,
+  ) -> (Direction, Integer) {
+    (Direction::FromRight, random_offset(interval, boundary, rand))
+  }
+}
+
+/// Clamps the random step toward `direction`'s endpoint — skewing the draw
+/// toward small offsets so identifiers cluster near the favored bound — and
+/// lets the effective boundary grow with `depth` (by `growth` per level), so
+/// gaps near frequently-edited, deep regions stay sparse instead of
+/// shrinking to a single slot.
+pub struct BoundaryStrategy {
+  pub direction: Direction,
+  pub growth: u32,
+}
+
+impl BoundaryStrategy {
+  pub fn new(direction: Direction, growth: u32) -> BoundaryStrategy {
+    BoundaryStrategy { direction, growth }
+  }
+}
+
+impl AllocationStrategy for BoundaryStrategy {
+  fn allocate(
+    &self,
+    depth: u32,
+    _left: &Integer,
+    _right: &Integer,
+    interval: &Integer,
+    boundary: u32,
+    rand: &mut RandState,
+  ) -> (Direction, Integer) {
+    let grown_boundary = boundary + self.growth * depth;
+    (self.direction, skewed_offset(interval, grown_boundary, rand))
+  }
+}
+
+/// Picks the default strategy for `depth` as a pure function of `depth`
+/// and a `seed` shared by every replica, so any two generators constructed
+/// with the same seed agree on the strategy at every depth without
+/// communicating.
+pub fn default_strategy_for_depth(depth: u32, seed: u64) -> Box<dyn AllocationStrategy> {
+  let mut x = (depth as u64) ^ seed;
+  // xorshift64
+  x ^= x << 13;
+  x ^= x >> 7;
+  x ^= x << 17;
+
+  if x % 2 == 0 {
+    Box::new(AddFromLeft)
+  } else {
+    Box::new(SubtractFromRight)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rug::rand::RandState;
+  use rug::Integer;
+
+  use super::default_strategy_for_depth;
+
+  fn direction_at(depth: u32, seed: u64) -> super::Direction {
+    let mut rand = RandState::new();
+    let (direction, _) = default_strategy_for_depth(depth, seed).allocate(
+      depth,
+      &Integer::from(0),
+      &Integer::from(100),
+      &Integer::from(100),
+      10,
+      &mut rand,
+    );
+
+    direction
+  }
+
+  #[test]
+  fn same_seed_agrees_at_every_depth() {
+    for depth in 0..64 {
+      assert_eq!(direction_at(depth, 42), direction_at(depth, 42));
+    }
+  }
+
+  #[test]
+  fn direction_is_a_function_of_depth_and_seed() {
+    // A constant (seed/depth-ignoring) strategy would pass
+    // `same_seed_agrees_at_every_depth` trivially; assert the direction
+    // actually varies across seeds and across depths for some inputs.
+    let varies_with_seed = (0..64).any(|depth| direction_at(depth, 1) != direction_at(depth, 2));
+    assert!(varies_with_seed, "direction never changed across seeds");
+
+    let varies_with_depth = (1..64).any(|depth| direction_at(depth, 42) != direction_at(0, 42));
+    assert!(varies_with_depth, "direction never changed across depths");
   }
 }